@@ -1,12 +1,97 @@
+use std::collections::VecDeque;
 use std::env;
 use std::fs;
-use std::io::{self, Write};
-use std::path::Path;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use serde::Serialize;
+
+#[cfg(target_os = "linux")]
+use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
+
+// No-op outside Linux, or if inotify setup fails; the caller's poll loop still covers it.
+fn watch_file(path: &Path, tx: mpsc::Sender<()>) {
+    #[cfg(target_os = "linux")]
+    {
+        spawn_inotify(path, tx);
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (path, tx);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn spawn_inotify(path: &Path, tx: mpsc::Sender<()>) {
+    let inotify = match Inotify::init() {
+        Ok(inotify) => inotify,
+        Err(_) => return,
+    };
+
+    let watching_file = path.exists();
+    let wd = if watching_file {
+        inotify
+            .watches()
+            .add(path, WatchMask::MODIFY | WatchMask::CLOSE_WRITE)
+    } else {
+        let parent = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        inotify.watches().add(parent, WatchMask::CREATE)
+    };
+    let wd = match wd {
+        Ok(wd) => wd,
+        Err(_) => return,
+    };
+
+    let path = path.to_path_buf();
+    thread::spawn(move || run_inotify(inotify, wd, watching_file, path, tx));
+}
+
+// Runs until the process exits or a read fails: the inotify blocking read has no
+// timeout of its own, so this can't be folded into the sampling loop.
+#[cfg(target_os = "linux")]
+fn run_inotify(
+    mut inotify: Inotify,
+    mut wd: WatchDescriptor,
+    mut watching_file: bool,
+    path: PathBuf,
+    tx: mpsc::Sender<()>,
+) {
+    let file_name = path.file_name().map(|n| n.to_os_string());
+    let mut buffer = [0; 1024];
+
+    loop {
+        let events = match inotify.read_events_blocking(&mut buffer) {
+            Ok(events) => events,
+            Err(_) => return,
+        };
+
+        for event in events {
+            if watching_file {
+                let _ = tx.send(());
+            } else if event.mask.contains(EventMask::CREATE) && event.name == file_name.as_deref() {
+                if let Ok(new_wd) = inotify
+                    .watches()
+                    .add(&path, WatchMask::MODIFY | WatchMask::CLOSE_WRITE)
+                {
+                    let _ = inotify.watches().remove(wd.clone());
+                    wd = new_wd;
+                    watching_file = true;
+                }
+                let _ = tx.send(());
+            }
+        }
+    }
+}
+
 fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -42,29 +127,594 @@ fn get_file_size(path: &Path) -> io::Result<u64> {
     fs::metadata(path).map(|m| m.len())
 }
 
-fn print_summary(initial_size: Option<u64>, final_size: u64, elapsed_secs: u64) {
-    let total_downloaded = final_size.saturating_sub(initial_size.unwrap_or(0));
+struct ThroughputEstimator {
+    window: Duration,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl ThroughputEstimator {
+    fn new(window: Duration) -> Self {
+        ThroughputEstimator {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    fn record(&mut self, now: Instant, size: u64) {
+        self.samples.push_back((now, size));
+        while let Some(&(oldest, _)) = self.samples.front() {
+            if now.duration_since(oldest) > self.window {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Bytes/sec across the samples currently in the window.
+    fn current_rate(&self) -> f64 {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(&(oldest_time, oldest_size)), Some(&(newest_time, newest_size)))
+                if newest_time > oldest_time =>
+            {
+                newest_size.saturating_sub(oldest_size) as f64
+                    / (newest_time - oldest_time).as_secs_f64()
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod throughput_estimator_tests {
+    use super::*;
+
+    #[test]
+    fn zero_with_no_samples() {
+        let estimator = ThroughputEstimator::new(Duration::from_secs(10));
+        assert_eq!(estimator.current_rate(), 0.0);
+    }
+
+    #[test]
+    fn zero_with_a_single_sample() {
+        let mut estimator = ThroughputEstimator::new(Duration::from_secs(10));
+        estimator.record(Instant::now(), 100);
+        assert_eq!(estimator.current_rate(), 0.0);
+    }
 
+    #[test]
+    fn reflects_growth_across_the_window() {
+        let mut estimator = ThroughputEstimator::new(Duration::from_secs(10));
+        let start = Instant::now();
+        estimator.record(start, 0);
+        estimator.record(start + Duration::from_secs(2), 2000);
+        assert_eq!(estimator.current_rate(), 1000.0);
+    }
+
+    #[test]
+    fn evicts_samples_older_than_the_window() {
+        let mut estimator = ThroughputEstimator::new(Duration::from_secs(10));
+        let start = Instant::now();
+        estimator.record(start, 0);
+        estimator.record(start + Duration::from_secs(5), 500);
+        estimator.record(start + Duration::from_secs(11), 1100);
+
+        // The +0s sample is now more than the 10s window behind +11s, so
+        // the rate comes from +5s..+11s, not +0s..+11s.
+        assert_eq!(estimator.current_rate(), (1100 - 500) as f64 / 6.0);
+    }
+}
+
+struct FileSummary {
+    path: PathBuf,
+    initial_size: Option<u64>,
+    final_size: u64,
+    expected_total: Option<u64>,
+}
+
+fn print_summary(summaries: &[FileSummary], elapsed_secs: u64) {
     println!("\n\n--- Download Summary ---");
-    println!("Total downloaded: {}", format_bytes(total_downloaded));
-    println!("Final size: {}", format_bytes(final_size));
-    println!("Duration: {}", format_duration(elapsed_secs));
+
+    let mut combined_downloaded: u64 = 0;
+
+    for summary in summaries {
+        let downloaded = summary
+            .final_size
+            .saturating_sub(summary.initial_size.unwrap_or(0));
+        combined_downloaded += downloaded;
+
+        println!("\n{}:", summary.path.display());
+        println!("  Downloaded: {}", format_bytes(downloaded));
+        println!("  Final size: {}", format_bytes(summary.final_size));
+
+        if let Some(total) = summary.expected_total {
+            if summary.final_size >= total {
+                println!("  Reached expected total size ({})", format_bytes(total));
+            } else {
+                println!(
+                    "  Did not reach expected total size: {} of {} ({:.1}%)",
+                    format_bytes(summary.final_size),
+                    format_bytes(total),
+                    summary.final_size as f64 / total as f64 * 100.0
+                );
+            }
+        }
+    }
+
+    println!("\nDuration: {}", format_duration(elapsed_secs));
+    println!("Total downloaded: {}", format_bytes(combined_downloaded));
 
     if elapsed_secs > 0 {
-        let avg_speed = total_downloaded / elapsed_secs;
-        println!("Average speed: {}/s", format_bytes(avg_speed));
+        let avg_speed = combined_downloaded / elapsed_secs;
+        println!("Combined average speed: {}/s", format_bytes(avg_speed));
+    }
+}
+
+struct Args {
+    file_paths: Vec<PathBuf>,
+    total_size: Option<u64>,
+    no_progress: bool,
+    json: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut file_paths = Vec::new();
+    let mut total_size = None;
+    let mut no_progress = false;
+    let mut json = false;
+    let mut iter = args.iter().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--total" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--total requires a byte count".to_string())?;
+                total_size = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid --total value: {}", value))?,
+                );
+            }
+            "--no-progress" => no_progress = true,
+            "--json" => json = true,
+            "--format" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--format requires a value".to_string())?;
+                match value.as_str() {
+                    "json" => json = true,
+                    "pretty" => json = false,
+                    other => return Err(format!("unknown --format value: {}", other)),
+                }
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("unexpected argument: {}", other))
+            }
+            other => file_paths.push(PathBuf::from(other)),
+        }
+    }
+
+    if file_paths.is_empty() {
+        return Err("missing <file_path>".to_string());
+    }
+
+    Ok(Args {
+        file_paths,
+        total_size,
+        no_progress,
+        json,
+    })
+}
+
+#[cfg(test)]
+mod parse_args_tests {
+    use super::*;
+
+    fn args(words: &[&str]) -> Vec<String> {
+        std::iter::once("zpd".to_string())
+            .chain(words.iter().map(|w| w.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn requires_at_least_one_file_path() {
+        assert_eq!(parse_args(&args(&[])).unwrap_err(), "missing <file_path>");
+    }
+
+    #[test]
+    fn collects_multiple_file_paths() {
+        let parsed = parse_args(&args(&["a.bin", "b.bin"])).unwrap();
+        assert_eq!(
+            parsed.file_paths,
+            vec![PathBuf::from("a.bin"), PathBuf::from("b.bin")]
+        );
+    }
+
+    #[test]
+    fn parses_total_flag() {
+        let parsed = parse_args(&args(&["a.bin", "--total", "1024"])).unwrap();
+        assert_eq!(parsed.total_size, Some(1024));
+    }
+
+    #[test]
+    fn rejects_invalid_total_value() {
+        let err = parse_args(&args(&["a.bin", "--total", "nope"])).unwrap_err();
+        assert!(err.contains("invalid --total value"));
+    }
+
+    #[test]
+    fn format_json_enables_json_output() {
+        let parsed = parse_args(&args(&["a.bin", "--format", "json"])).unwrap();
+        assert!(parsed.json);
+    }
+
+    #[test]
+    fn rejects_unrecognized_flags_instead_of_treating_them_as_paths() {
+        let err = parse_args(&args(&["a.bin", "--no-progres"])).unwrap_err();
+        assert_eq!(err, "unexpected argument: --no-progres");
+    }
+}
+
+// Paths that don't exist yet are kept as-is, so "waiting for file to appear" still works for them.
+fn expand_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut expanded = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            match fs::read_dir(path) {
+                Ok(entries) => {
+                    for entry in entries.flatten() {
+                        let entry_path = entry.path();
+                        if entry_path.is_file() {
+                            expanded.push(entry_path);
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Warning: could not read directory {}: {}",
+                        path.display(),
+                        e
+                    )
+                }
+            }
+        } else {
+            expanded.push(path.clone());
+        }
+    }
+
+    expanded
+}
+
+#[cfg(test)]
+mod expand_paths_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering as AtomicOrdering};
+
+    fn unique_temp_dir() -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, AtomicOrdering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("zpd-expand-paths-test-{}-{}", std::process::id(), n));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn expands_a_directory_into_its_files() {
+        let dir = unique_temp_dir();
+        fs::write(dir.join("a.bin"), b"a").unwrap();
+        fs::write(dir.join("b.bin"), b"bb").unwrap();
+
+        let mut expanded = expand_paths(&[dir.clone()]);
+        expanded.sort();
+
+        assert_eq!(expanded, vec![dir.join("a.bin"), dir.join("b.bin")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn leaves_non_directory_paths_untouched() {
+        let path = PathBuf::from("does-not-exist.bin");
+        assert_eq!(expand_paths(&[path.clone()]), vec![path]);
+    }
+
+    #[test]
+    fn skips_subdirectories_inside_an_expanded_directory() {
+        let dir = unique_temp_dir();
+        fs::write(dir.join("a.bin"), b"a").unwrap();
+        fs::create_dir(dir.join("subdir")).unwrap();
+
+        assert_eq!(expand_paths(&[dir.clone()]), vec![dir.join("a.bin")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+enum Progress {
+    Bars {
+        files: Vec<ProgressBar>,
+        aggregate: ProgressBar,
+    },
+    Plain {
+        multi: bool,
+    },
+}
+
+impl Progress {
+    fn new(paths: &[PathBuf], total_size: Option<u64>, enabled: bool) -> Self {
+        if !enabled {
+            return Progress::Plain {
+                multi: paths.len() > 1,
+            };
+        }
+
+        // A per-file --total only makes sense when there's a single file;
+        // with several files it can't mean all of them at once.
+        let per_file_total = if paths.len() == 1 { total_size } else { None };
+
+        let multi = MultiProgress::new();
+        let files = paths
+            .iter()
+            .map(|_| multi.add(Self::new_bar(per_file_total)))
+            .collect();
+        let aggregate = multi.add(Self::new_bar(None));
+
+        Progress::Bars { files, aggregate }
+    }
+
+    fn new_bar(total: Option<u64>) -> ProgressBar {
+        match total {
+            Some(total) => {
+                let bar = ProgressBar::new(total);
+                bar.set_style(
+                    ProgressStyle::with_template(
+                        "{bar:40.cyan/blue} {bytes}/{total_bytes} {bytes_per_sec} {eta} {msg}",
+                    )
+                    .unwrap()
+                    .progress_chars("#>-"),
+                );
+                bar
+            }
+            None => {
+                let bar = ProgressBar::new_spinner();
+                bar.set_style(ProgressStyle::with_template("{spinner} {wide_msg}").unwrap());
+                bar.enable_steady_tick(Duration::from_millis(120));
+                bar
+            }
+        }
+    }
+
+    fn tick_file(&self, index: usize, position: u64, message: String) {
+        match self {
+            Progress::Bars { files, .. } => {
+                files[index].set_position(position);
+                files[index].set_message(message);
+            }
+            Progress::Plain { multi: true } => println!("{}", message),
+            Progress::Plain { multi: false } => {
+                print!("\r{}    ", message);
+                io::stdout().flush().unwrap();
+            }
+        }
+    }
+
+    fn tick_aggregate(&self, message: String) {
+        match self {
+            Progress::Bars { aggregate, .. } => aggregate.set_message(message),
+            Progress::Plain { multi: true } => println!("{}", message),
+            Progress::Plain { multi: false } => {}
+        }
+    }
+
+    fn println(&self, line: &str) {
+        match self {
+            Progress::Bars { aggregate, .. } => aggregate.println(line),
+            Progress::Plain { .. } => println!("{}", line),
+        }
+    }
+
+    fn finish(&self) {
+        if let Progress::Bars { files, aggregate } = self {
+            for bar in files {
+                bar.finish_and_clear();
+            }
+            aggregate.finish_and_clear();
+        }
+    }
+}
+
+// Modeled on solana's `DownloadProgressRecord`.
+#[derive(Serialize)]
+struct SampleRecord {
+    path: String,
+    elapsed_secs: f64,
+    bytes: u64,
+    current_throughput: f64,
+    average_throughput: f64,
+    idle: bool,
+}
+
+#[derive(Serialize)]
+struct AggregateRecord {
+    elapsed_secs: f64,
+    bytes: u64,
+    current_throughput: f64,
+}
+
+#[derive(Serialize)]
+struct SummaryFileRecord {
+    path: String,
+    initial_bytes: Option<u64>,
+    final_bytes: u64,
+    expected_total: Option<u64>,
+    reached_total: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct SummaryJsonRecord {
+    elapsed_secs: u64,
+    total_downloaded: u64,
+    average_throughput: f64,
+    files: Vec<SummaryFileRecord>,
+}
+
+fn print_json_summary(summaries: &[FileSummary], elapsed_secs: u64) {
+    let mut total_downloaded: u64 = 0;
+    let files = summaries
+        .iter()
+        .map(|summary| {
+            let downloaded = summary
+                .final_size
+                .saturating_sub(summary.initial_size.unwrap_or(0));
+            total_downloaded += downloaded;
+            SummaryFileRecord {
+                path: summary.path.display().to_string(),
+                initial_bytes: summary.initial_size,
+                final_bytes: summary.final_size,
+                expected_total: summary.expected_total,
+                reached_total: summary
+                    .expected_total
+                    .map(|total| summary.final_size >= total),
+            }
+        })
+        .collect();
+
+    let average_throughput = if elapsed_secs > 0 {
+        total_downloaded as f64 / elapsed_secs as f64
+    } else {
+        0.0
+    };
+
+    let record = SummaryJsonRecord {
+        elapsed_secs,
+        total_downloaded,
+        average_throughput,
+        files,
+    };
+
+    if let Ok(line) = serde_json::to_string(&record) {
+        println!("{}", line);
+    }
+}
+
+enum Output {
+    Pretty(Progress),
+    Json,
+}
+
+impl Output {
+    fn new(paths: &[PathBuf], total_size: Option<u64>, progress_enabled: bool, json: bool) -> Self {
+        if json {
+            Output::Json
+        } else {
+            Output::Pretty(Progress::new(paths, total_size, progress_enabled))
+        }
+    }
+
+    fn sample(&self, index: usize, record: &SampleRecord, message: String) {
+        match self {
+            Output::Pretty(progress) => progress.tick_file(index, record.bytes, message),
+            Output::Json => {
+                if let Ok(line) = serde_json::to_string(record) {
+                    println!("{}", line);
+                }
+            }
+        }
+    }
+
+    fn waiting(&self, index: usize, message: String) {
+        if let Output::Pretty(progress) = self {
+            progress.tick_file(index, 0, message);
+        }
+    }
+
+    fn aggregate(&self, record: &AggregateRecord, message: String) {
+        match self {
+            Output::Pretty(progress) => progress.tick_aggregate(message),
+            Output::Json => {
+                if let Ok(line) = serde_json::to_string(record) {
+                    println!("{}", line);
+                }
+            }
+        }
+    }
+
+    fn note(&self, line: &str) {
+        if let Output::Pretty(progress) = self {
+            progress.println(line);
+        }
+    }
+
+    fn finish(&self) {
+        if let Output::Pretty(progress) = self {
+            progress.finish();
+        }
+    }
+
+    fn summary(&self, summaries: &[FileSummary], elapsed_secs: u64) {
+        match self {
+            Output::Pretty(_) => print_summary(summaries, elapsed_secs),
+            Output::Json => print_json_summary(summaries, elapsed_secs),
+        }
+    }
+}
+
+struct FileTracker {
+    path: PathBuf,
+    throughput: ThroughputEstimator,
+    previous_size: Option<u64>,
+    initial_size: Option<u64>,
+    last_known_size: u64,
+    no_change_count: u32,
+    done: bool,
+}
+
+impl FileTracker {
+    fn new(path: PathBuf) -> Self {
+        FileTracker {
+            path,
+            throughput: ThroughputEstimator::new(Duration::from_secs(10)),
+            previous_size: None,
+            initial_size: None,
+            last_known_size: 0,
+            no_change_count: 0,
+            done: false,
+        }
+    }
+
+    fn summary(&self, expected_total: Option<u64>) -> FileSummary {
+        FileSummary {
+            path: self.path.clone(),
+            initial_size: self.initial_size,
+            final_size: self.last_known_size,
+            expected_total,
+        }
     }
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    if args.len() != 2 {
-        eprintln!("Usage: {} <file_path>", args[0]);
+    let parsed_args = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!(
+                "Usage: {} <file_path>... [--total <bytes>] [--no-progress] [--json | --format json]",
+                args[0]
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let file_paths = expand_paths(&parsed_args.file_paths);
+    if file_paths.is_empty() {
+        eprintln!("No files to watch: directory arguments expanded to nothing");
         std::process::exit(1);
     }
-
-    let file_path = Path::new(&args[1]);
+    let total_size = parsed_args.total_size;
+    let json = parsed_args.json;
+    let progress_enabled = !parsed_args.no_progress && io::stdout().is_terminal();
 
     // Set up Ctrl+C handler
     let running = Arc::new(AtomicBool::new(true));
@@ -75,68 +725,174 @@ fn main() {
     })
     .expect("Error setting Ctrl+C handler");
 
-    println!("Monitoring download speed for: {}", file_path.display());
-    println!("Press Ctrl+C to stop (auto-exits after 5s of no activity)\n");
+    if !json {
+        for path in &file_paths {
+            println!("Monitoring download speed for: {}", path.display());
+        }
+        println!("Press Ctrl+C to stop (auto-exits after 5s of no activity)\n");
+    }
 
-    let mut previous_size: Option<u64> = None;
-    let mut initial_size: Option<u64> = None;
-    let mut last_known_size: u64 = 0;
-    let mut no_change_count = 0;
     let start_time = Instant::now();
+    let output = Output::new(&file_paths, total_size, progress_enabled, json);
+    let (tx, rx) = mpsc::channel();
+    let mut trackers: Vec<FileTracker> = file_paths
+        .into_iter()
+        .map(|path| {
+            watch_file(&path, tx.clone());
+            FileTracker::new(path)
+        })
+        .collect();
+    let per_file_total = if trackers.len() == 1 {
+        total_size
+    } else {
+        None
+    };
+    // Only a full second without any inotify wakeup counts towards a file's
+    // idle count, so writes to another watched file can't hurry it along.
+    let mut quiet_second = false;
 
-    while running.load(Ordering::SeqCst) {
-        match get_file_size(file_path) {
-            Ok(current_size) => {
-                last_known_size = current_size;
+    while running.load(Ordering::SeqCst) && trackers.iter().any(|t| !t.done) {
+        let mut aggregate_downloaded: u64 = 0;
+        let mut aggregate_rate: f64 = 0.0;
 
-                if initial_size.is_none() {
-                    initial_size = Some(current_size);
-                }
+        for (index, tracker) in trackers.iter_mut().enumerate() {
+            if tracker.done {
+                aggregate_downloaded += tracker
+                    .last_known_size
+                    .saturating_sub(tracker.initial_size.unwrap_or(0));
+                continue;
+            }
 
-                if let Some(prev) = previous_size {
-                    let delta = current_size.saturating_sub(prev);
+            match get_file_size(&tracker.path) {
+                Ok(current_size) => {
+                    tracker.last_known_size = current_size;
+                    let now = Instant::now();
 
-                    if delta == 0 {
-                        no_change_count += 1;
-                        print!(
-                            "\rSize: {} | Speed: 0 B/s (idle {}/5)    ",
-                            format_bytes(current_size),
-                            no_change_count
-                        );
-                    } else {
-                        no_change_count = 0;
-                        print!(
-                            "\rSize: {} | Speed: {}/s         ",
+                    if tracker.initial_size.is_none() {
+                        tracker.initial_size = Some(current_size);
+                    }
+
+                    if let Some(prev) = tracker.previous_size {
+                        let delta = current_size.saturating_sub(prev);
+                        tracker.throughput.record(now, current_size);
+
+                        if delta != 0 {
+                            tracker.no_change_count = 0;
+                        } else if quiet_second {
+                            tracker.no_change_count += 1;
+                        }
+
+                        let idle_suffix = if tracker.no_change_count > 0 {
+                            format!(" (idle {}/5)", tracker.no_change_count)
+                        } else {
+                            String::new()
+                        };
+
+                        let progress_suffix = match per_file_total {
+                            Some(total) if total > 0 => {
+                                let pct = current_size.min(total) as f64 / total as f64 * 100.0;
+                                let rate = tracker.throughput.current_rate();
+                                let eta = if rate > 0.0 {
+                                    let remaining = total.saturating_sub(current_size);
+                                    format_duration((remaining as f64 / rate).round() as u64)
+                                } else {
+                                    "unknown".to_string()
+                                };
+                                format!(" | {:.1}% | ETA: {}", pct, eta)
+                            }
+                            _ => String::new(),
+                        };
+
+                        let message = format!(
+                            "{}: {} | {}/s{}{}",
+                            tracker.path.display(),
                             format_bytes(current_size),
-                            format_bytes(delta)
+                            format_bytes(tracker.throughput.current_rate() as u64),
+                            progress_suffix,
+                            idle_suffix
                         );
-                    }
-                    io::stdout().flush().unwrap();
+                        let elapsed_secs = start_time.elapsed().as_secs_f64();
+                        let downloaded =
+                            current_size.saturating_sub(tracker.initial_size.unwrap_or(0));
+                        let record = SampleRecord {
+                            path: tracker.path.display().to_string(),
+                            elapsed_secs,
+                            bytes: current_size,
+                            current_throughput: tracker.throughput.current_rate(),
+                            average_throughput: if elapsed_secs > 0.0 {
+                                downloaded as f64 / elapsed_secs
+                            } else {
+                                0.0
+                            },
+                            idle: tracker.no_change_count > 0,
+                        };
+                        output.sample(index, &record, message);
 
-                    if no_change_count >= 5 {
-                        print_summary(initial_size, current_size, start_time.elapsed().as_secs());
-                        return;
+                        // Treated as finished, not merely paused: file size alone can't
+                        // tell a completed download apart from one that's stalled, and
+                        // this tracker won't be polled again even if it later resumes.
+                        if tracker.no_change_count >= 5 {
+                            tracker.done = true;
+                        }
+                    } else {
+                        tracker.throughput.record(now, current_size);
+                        output.note(&format!(
+                            "{}: initial size {}",
+                            tracker.path.display(),
+                            format_bytes(current_size)
+                        ));
                     }
-                } else {
-                    println!("Initial size: {}", format_bytes(current_size));
+                    tracker.previous_size = Some(current_size);
                 }
-                previous_size = Some(current_size);
-            }
-            Err(e) => {
-                if previous_size.is_some() {
-                    println!("\nFile no longer accessible: {}", e);
-                    print_summary(initial_size, last_known_size, start_time.elapsed().as_secs());
-                    return;
-                } else {
-                    print!("\rWaiting for file to appear...    ");
-                    io::stdout().flush().unwrap();
+                Err(e) => {
+                    if tracker.previous_size.is_some() {
+                        output.note(&format!(
+                            "{}: no longer accessible: {}",
+                            tracker.path.display(),
+                            e
+                        ));
+                        tracker.done = true;
+                    } else {
+                        output.waiting(
+                            index,
+                            format!("{}: waiting for file to appear...", tracker.path.display()),
+                        );
+                    }
                 }
             }
+
+            aggregate_downloaded += tracker
+                .last_known_size
+                .saturating_sub(tracker.initial_size.unwrap_or(0));
+            aggregate_rate += tracker.throughput.current_rate();
+        }
+
+        let aggregate_record = AggregateRecord {
+            elapsed_secs: start_time.elapsed().as_secs_f64(),
+            bytes: aggregate_downloaded,
+            current_throughput: aggregate_rate,
+        };
+        output.aggregate(
+            &aggregate_record,
+            format!(
+                "Total: {} downloaded | {}/s",
+                format_bytes(aggregate_downloaded),
+                format_bytes(aggregate_rate as u64)
+            ),
+        );
+
+        if !trackers.iter().any(|t| !t.done) {
+            break;
         }
 
-        thread::sleep(Duration::from_secs(1));
+        quiet_second = rx.recv_timeout(Duration::from_secs(1)).is_err();
+        // A burst of writes fires one inotify wakeup per event; drain the
+        // backlog so it collapses into the single re-poll above instead of
+        // spinning the loop once per event.
+        while rx.try_recv().is_ok() {}
     }
 
-    // Ctrl+C was pressed
-    print_summary(initial_size, last_known_size, start_time.elapsed().as_secs());
+    output.finish();
+    let summaries: Vec<FileSummary> = trackers.iter().map(|t| t.summary(per_file_total)).collect();
+    output.summary(&summaries, start_time.elapsed().as_secs());
 }